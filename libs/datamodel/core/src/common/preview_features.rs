@@ -0,0 +1,43 @@
+use std::fmt;
+
+/// A preview feature that can be turned on in a generator block ahead of general
+/// availability. A non-empty `previewFeatures` array in a datasource block is
+/// rejected.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PreviewFeature {
+    /// Lets a datasource block opt into PlanetScale's relation mode instead of
+    /// foreign keys.
+    PlanetScaleMode,
+    /// Lets a schema define more than one named `datasource` block and route
+    /// individual models to one of them via `@@datasource("name")`.
+    NamedDatasources,
+}
+
+/// `(variant, name-as-used-in-previewFeatures-arrays-and-the-CLI)`.
+const PREVIEW_FEATURES: &[(PreviewFeature, &str)] = &[
+    (PreviewFeature::PlanetScaleMode, "planetScaleMode"),
+    (PreviewFeature::NamedDatasources, "namedDatasources"),
+];
+
+impl PreviewFeature {
+    pub fn parse_opt(name: &str) -> Option<PreviewFeature> {
+        PREVIEW_FEATURES
+            .iter()
+            .find(|(_, feature_name)| *feature_name == name)
+            .map(|(feature, _)| *feature)
+    }
+
+    pub fn name(&self) -> &'static str {
+        PREVIEW_FEATURES
+            .iter()
+            .find(|(feature, _)| feature == self)
+            .map(|(_, name)| *name)
+            .expect("every PreviewFeature variant must have an entry in PREVIEW_FEATURES")
+    }
+}
+
+impl fmt::Display for PreviewFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
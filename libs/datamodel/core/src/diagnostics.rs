@@ -0,0 +1,368 @@
+use crate::ast::Span;
+use std::fmt;
+
+/// An error that occurred while validating a datamodel. Each variant carries enough
+/// context to render a helpful message and point at the offending span in the source
+/// file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DatamodelError {
+    ValidationError {
+        message: String,
+        span: Span,
+    },
+    TypeNotFoundError {
+        type_name: String,
+        span: Span,
+    },
+    SourceValidationError {
+        message: String,
+        source: String,
+        span: Span,
+    },
+    SourceArgumentNotFoundError {
+        argument_name: String,
+        source_name: String,
+        span: Span,
+    },
+    FunctionalEvaluationError {
+        message: String,
+        span: Span,
+    },
+    EnvironmentFunctionalEvaluationError {
+        var_name: String,
+        span: Span,
+    },
+    DatasourceProviderNotKnownError {
+        provider: String,
+        span: Span,
+    },
+    ConnectorError {
+        message: String,
+        span: Span,
+    },
+}
+
+impl DatamodelError {
+    pub fn new_validation_error(message: &str, span: Span) -> Self {
+        Self::ValidationError {
+            message: message.to_owned(),
+            span,
+        }
+    }
+
+    pub fn new_type_not_found_error(type_name: &str, span: Span) -> Self {
+        Self::TypeNotFoundError {
+            type_name: type_name.to_owned(),
+            span,
+        }
+    }
+
+    pub fn new_source_validation_error(message: &str, source: &str, span: Span) -> Self {
+        Self::SourceValidationError {
+            message: message.to_owned(),
+            source: source.to_owned(),
+            span,
+        }
+    }
+
+    pub fn new_source_argument_not_found_error(argument_name: &str, source_name: &str, span: Span) -> Self {
+        Self::SourceArgumentNotFoundError {
+            argument_name: argument_name.to_owned(),
+            source_name: source_name.to_owned(),
+            span,
+        }
+    }
+
+    pub fn new_functional_evaluation_error(message: &str, span: Span) -> Self {
+        Self::FunctionalEvaluationError {
+            message: message.to_owned(),
+            span,
+        }
+    }
+
+    pub fn new_datasource_provider_not_known_error(provider: &str, span: Span) -> Self {
+        Self::DatasourceProviderNotKnownError {
+            provider: provider.to_owned(),
+            span,
+        }
+    }
+
+    pub fn new_connector_error(message: &str, span: Span) -> Self {
+        Self::ConnectorError {
+            message: message.to_owned(),
+            span,
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Self::ValidationError { span, .. }
+            | Self::TypeNotFoundError { span, .. }
+            | Self::SourceValidationError { span, .. }
+            | Self::SourceArgumentNotFoundError { span, .. }
+            | Self::FunctionalEvaluationError { span, .. }
+            | Self::EnvironmentFunctionalEvaluationError { span, .. }
+            | Self::DatasourceProviderNotKnownError { span, .. }
+            | Self::ConnectorError { span, .. } => *span,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            Self::ValidationError { message, .. } => message.clone(),
+            Self::TypeNotFoundError { type_name, .. } => format!("Type \"{}\" is neither a built-in type, nor refers to another model, custom type, or enum.", type_name),
+            Self::SourceValidationError { message, source, .. } => format!("Error validating datasource `{}`: {}", source, message),
+            Self::SourceArgumentNotFoundError { argument_name, source_name, .. } => {
+                format!("Argument \"{}\" is missing in data source block \"{}\".", argument_name, source_name)
+            }
+            Self::FunctionalEvaluationError { message, .. } => message.clone(),
+            Self::EnvironmentFunctionalEvaluationError { var_name, .. } => {
+                format!("Environment variable not found: {}.", var_name)
+            }
+            Self::DatasourceProviderNotKnownError { provider, .. } => format!("Datasource provider not known: \"{}\".", provider),
+            Self::ConnectorError { message, .. } => message.clone(),
+        }
+    }
+}
+
+impl fmt::Display for DatamodelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+/// Whether a diagnostic is fatal to validation (`Error`) or merely advisory
+/// (`Warning`). Callers use this to decide whether a schema is still usable in spite
+/// of the diagnostics it produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+/// A non-fatal diagnostic: something worth surfacing to the user (an editor, the
+/// CLI) without blocking validation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatamodelWarning {
+    message: String,
+    span: Span,
+}
+
+impl DatamodelWarning {
+    pub fn new(message: String, span: Span) -> Self {
+        Self { message, span }
+    }
+
+    pub fn new_preview_features_empty_array_warning(span: Span) -> Self {
+        Self::new(
+            "The `previewFeatures` argument in a datasource block is empty and has no effect; remove it.".to_owned(),
+            span,
+        )
+    }
+
+    pub fn new_shadow_database_url_missing_env_var_warning(var_name: &str, span: Span) -> Self {
+        Self::new(
+            format!(
+                "The `shadowDatabaseUrl` was left unset because the environment variable \"{}\" is missing.",
+                var_name
+            ),
+            span,
+        )
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for DatamodelWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// A single diagnostic, tagged with its severity, as returned by `Diagnostics::all`.
+/// Merging both `errors` and `warnings` behind one type lets a caller (e.g. an
+/// editor integration) render them as one span-ordered list without caring which
+/// vec a given diagnostic originally came from.
+#[derive(Debug, Clone)]
+pub struct TaggedDiagnostic {
+    pub severity: DiagnosticSeverity,
+    pub span: Span,
+    message: String,
+}
+
+impl TaggedDiagnostic {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for TaggedDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.severity {
+            DiagnosticSeverity::Error => "error",
+            DiagnosticSeverity::Warning => "warning",
+        };
+        write!(f, "{}: {}", label, self.message)
+    }
+}
+
+/// All diagnostics accumulated while validating a single schema.
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    errors: Vec<DatamodelError>,
+    warnings: Vec<DatamodelWarning>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Diagnostics::default()
+    }
+
+    pub fn push_error(&mut self, error: DatamodelError) {
+        self.errors.push(error);
+    }
+
+    pub fn push_warning(&mut self, warning: DatamodelWarning) {
+        self.warnings.push(warning);
+    }
+
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    pub fn errors(&self) -> &[DatamodelError] {
+        &self.errors
+    }
+
+    pub fn warnings(&self) -> &[DatamodelWarning] {
+        &self.warnings
+    }
+
+    /// Deduplicates errors that carry the exact same `(span, message)` pair and sorts
+    /// the remainder by source span. This means, for instance, that an error reported
+    /// twice at the same span appears once, and that twenty fields referencing the
+    /// same broken custom type (each at its own span) are reported in source order
+    /// instead of interleaved with everything else.
+    pub(crate) fn sort_and_dedupe(&mut self) {
+        self.errors
+            .sort_by_key(|error| (error.span().start, error.span().end, error.message()));
+        self.errors
+            .dedup_by_key(|error| (error.span().start, error.span().end, error.message()));
+    }
+
+    /// Merges errors and warnings into one list, tagged with their severity and
+    /// sorted by source span, for callers that want a single span-ordered view
+    /// (e.g. printing a schema's diagnostics inline with its source).
+    pub fn all(&self) -> Vec<TaggedDiagnostic> {
+        let mut all: Vec<TaggedDiagnostic> = self
+            .errors
+            .iter()
+            .map(|error| TaggedDiagnostic {
+                severity: DiagnosticSeverity::Error,
+                span: error.span(),
+                message: error.message(),
+            })
+            .chain(self.warnings.iter().map(|warning| TaggedDiagnostic {
+                severity: DiagnosticSeverity::Warning,
+                span: warning.span(),
+                message: warning.message().to_owned(),
+            }))
+            .collect();
+
+        all.sort_by_key(|diagnostic| (diagnostic.span.start, diagnostic.span.end));
+        all
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, diagnostic) in self.all().iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", diagnostic)?;
+        }
+        Ok(())
+    }
+}
+
+/// A named collection of `Diagnostics`, one per producing subsystem (e.g.
+/// `"datasource"`, `"types"`, `"names"`). This lets a caller present a schema's
+/// errors and warnings grouped by the part of validation that found them, rather
+/// than interleaved into a single flat list.
+#[derive(Debug, Default)]
+pub struct DiagnosticsBatch {
+    batches: Vec<(&'static str, Diagnostics)>,
+}
+
+impl DiagnosticsBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, subsystem: &'static str, diagnostics: Diagnostics) {
+        self.batches.push((subsystem, diagnostics));
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.batches.iter().any(|(_, diagnostics)| diagnostics.has_errors())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &Diagnostics)> {
+        self.batches.iter().map(|(subsystem, diagnostics)| (*subsystem, diagnostics))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: usize, end: usize) -> Span {
+        Span::new(start, end)
+    }
+
+    #[test]
+    fn sort_and_dedupe_removes_exact_duplicates_and_sorts_by_span() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push_error(DatamodelError::new_validation_error("second", span(10, 20)));
+        diagnostics.push_error(DatamodelError::new_validation_error("first", span(0, 5)));
+        diagnostics.push_error(DatamodelError::new_validation_error("first", span(0, 5)));
+
+        diagnostics.sort_and_dedupe();
+
+        let messages: Vec<String> = diagnostics.errors().iter().map(|error| error.message()).collect();
+        assert_eq!(messages, vec!["first".to_owned(), "second".to_owned()]);
+    }
+
+    #[test]
+    fn sort_and_dedupe_keeps_same_span_distinct_messages() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push_error(DatamodelError::new_validation_error("a", span(0, 5)));
+        diagnostics.push_error(DatamodelError::new_validation_error("b", span(0, 5)));
+
+        diagnostics.sort_and_dedupe();
+
+        assert_eq!(diagnostics.errors().len(), 2);
+    }
+
+    #[test]
+    fn all_merges_errors_and_warnings_tagged_with_severity_in_span_order() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push_error(DatamodelError::new_validation_error("an error", span(10, 20)));
+        diagnostics.push_warning(DatamodelWarning::new("a warning".to_owned(), span(0, 5)));
+
+        let all = diagnostics.all();
+
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].severity, DiagnosticSeverity::Warning);
+        assert_eq!(all[0].message(), "a warning");
+        assert_eq!(all[1].severity, DiagnosticSeverity::Error);
+        assert_eq!(all[1].message(), "an error");
+    }
+}
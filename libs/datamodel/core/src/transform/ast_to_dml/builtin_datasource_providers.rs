@@ -0,0 +1,222 @@
+use super::datasource_provider::DatasourceProvider;
+use crate::{ast::Span, configuration::StringFromEnvVar, diagnostics::DatamodelError};
+use datamodel_connector::Connector;
+
+pub(crate) struct MySqlDatasourceProvider;
+
+impl MySqlDatasourceProvider {
+    pub(crate) fn new() -> Self {
+        MySqlDatasourceProvider
+    }
+}
+
+impl DatasourceProvider for MySqlDatasourceProvider {
+    fn is_provider(&self, provider: &str) -> bool {
+        provider == "mysql"
+    }
+
+    fn canonical_name(&self) -> &'static str {
+        "mysql"
+    }
+
+    fn connector(&self) -> &'static dyn Connector {
+        sql_datamodel_connector::MYSQL
+    }
+
+    fn infer_from_url(&self, url: &str) -> bool {
+        url.starts_with("mysql://")
+    }
+
+    fn normalize_url(&self, url: StringFromEnvVar, _source_name: &str, _span: Span) -> Result<StringFromEnvVar, DatamodelError> {
+        Ok(url)
+    }
+}
+
+pub(crate) struct PostgresDatasourceProvider;
+
+impl PostgresDatasourceProvider {
+    pub(crate) fn new() -> Self {
+        PostgresDatasourceProvider
+    }
+}
+
+impl DatasourceProvider for PostgresDatasourceProvider {
+    fn is_provider(&self, provider: &str) -> bool {
+        provider == "postgresql" || provider == "postgres"
+    }
+
+    fn canonical_name(&self) -> &'static str {
+        "postgresql"
+    }
+
+    fn connector(&self) -> &'static dyn Connector {
+        sql_datamodel_connector::POSTGRES
+    }
+
+    fn infer_from_url(&self, url: &str) -> bool {
+        url.starts_with("postgresql://") || url.starts_with("postgres://")
+    }
+
+    fn normalize_url(&self, url: StringFromEnvVar, _source_name: &str, _span: Span) -> Result<StringFromEnvVar, DatamodelError> {
+        Ok(url)
+    }
+}
+
+pub(crate) struct SqliteDatasourceProvider;
+
+impl SqliteDatasourceProvider {
+    pub(crate) fn new() -> Self {
+        SqliteDatasourceProvider
+    }
+}
+
+impl DatasourceProvider for SqliteDatasourceProvider {
+    fn is_provider(&self, provider: &str) -> bool {
+        provider == "sqlite"
+    }
+
+    fn canonical_name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn connector(&self) -> &'static dyn Connector {
+        sql_datamodel_connector::SQLITE
+    }
+
+    fn infer_from_url(&self, url: &str) -> bool {
+        url.starts_with("file:")
+    }
+
+    fn normalize_url(&self, url: StringFromEnvVar, _source_name: &str, _span: Span) -> Result<StringFromEnvVar, DatamodelError> {
+        Ok(url)
+    }
+}
+
+pub(crate) struct MsSqlDatasourceProvider;
+
+impl MsSqlDatasourceProvider {
+    pub(crate) fn new() -> Self {
+        MsSqlDatasourceProvider
+    }
+}
+
+impl DatasourceProvider for MsSqlDatasourceProvider {
+    fn is_provider(&self, provider: &str) -> bool {
+        provider == "sqlserver"
+    }
+
+    fn canonical_name(&self) -> &'static str {
+        "sqlserver"
+    }
+
+    fn connector(&self) -> &'static dyn Connector {
+        sql_datamodel_connector::MSSQL
+    }
+
+    fn infer_from_url(&self, url: &str) -> bool {
+        url.starts_with("sqlserver://") || url.starts_with("jdbc:sqlserver://")
+    }
+
+    fn normalize_url(&self, url: StringFromEnvVar, source_name: &str, span: Span) -> Result<StringFromEnvVar, DatamodelError> {
+        // SQL Server connection strings are `;`-separated key/value pairs rather than a
+        // URL path, so a missing `database` parameter has to be caught explicitly: it
+        // silently defaults to `master` otherwise, which is almost never what's wanted.
+        if let Some(literal) = url.as_literal() {
+            let has_database = literal
+                .split(';')
+                .filter_map(|part| part.split_once('='))
+                .any(|(key, value)| key.trim().eq_ignore_ascii_case("database") && !value.trim().is_empty());
+
+            if !has_database {
+                return Err(DatamodelError::new_source_validation_error(
+                    "A sqlserver: connection string must specify a `database` parameter.",
+                    source_name,
+                    span,
+                ));
+            }
+        }
+
+        Ok(url)
+    }
+}
+
+#[cfg(test)]
+mod mssql_tests {
+    use super::*;
+
+    fn literal(value: &str) -> StringFromEnvVar {
+        StringFromEnvVar {
+            from_env_var: None,
+            value: value.to_owned(),
+        }
+    }
+
+    fn from_env(var_name: &str) -> StringFromEnvVar {
+        StringFromEnvVar {
+            from_env_var: Some(var_name.to_owned()),
+            value: String::new(),
+        }
+    }
+
+    #[test]
+    fn rejects_a_connection_string_missing_the_database_parameter() {
+        let provider = MsSqlDatasourceProvider::new();
+        let url = literal("sqlserver://localhost:1433;user=sa;password=secret");
+
+        let err = provider.normalize_url(url, "db", Span::new(0, 1)).unwrap_err();
+
+        assert_eq!(
+            err.message(),
+            "Error validating datasource `db`: A sqlserver: connection string must specify a `database` parameter."
+        );
+    }
+
+    #[test]
+    fn accepts_a_connection_string_with_the_database_parameter() {
+        let provider = MsSqlDatasourceProvider::new();
+        let url = literal("sqlserver://localhost:1433;database=mydb;user=sa;password=secret");
+
+        assert!(provider.normalize_url(url, "db", Span::new(0, 1)).is_ok());
+    }
+
+    #[test]
+    fn skips_validation_for_a_url_sourced_from_an_unresolved_env_var() {
+        // `as_literal()` only inspects a URL that was written directly in the schema;
+        // one read from `env()` isn't available at schema-parsing time, so it can't
+        // be validated here and is passed through unchecked.
+        let provider = MsSqlDatasourceProvider::new();
+        let url = from_env("DATABASE_URL");
+
+        assert!(provider.normalize_url(url, "db", Span::new(0, 1)).is_ok());
+    }
+}
+
+pub(crate) struct MongoDbDatasourceProvider;
+
+impl MongoDbDatasourceProvider {
+    pub(crate) fn new() -> Self {
+        MongoDbDatasourceProvider
+    }
+}
+
+impl DatasourceProvider for MongoDbDatasourceProvider {
+    fn is_provider(&self, provider: &str) -> bool {
+        provider == "mongodb"
+    }
+
+    fn canonical_name(&self) -> &'static str {
+        "mongodb"
+    }
+
+    fn connector(&self) -> &'static dyn Connector {
+        mongodb_datamodel_connector::MONGODB
+    }
+
+    fn infer_from_url(&self, url: &str) -> bool {
+        url.starts_with("mongodb://") || url.starts_with("mongodb+srv://")
+    }
+
+    fn normalize_url(&self, url: StringFromEnvVar, _source_name: &str, _span: Span) -> Result<StringFromEnvVar, DatamodelError> {
+        Ok(url)
+    }
+}
@@ -8,7 +8,7 @@ use super::{
 };
 use crate::{
     ast::SourceConfig,
-    diagnostics::{DatamodelError, Diagnostics},
+    diagnostics::{DatamodelError, DatamodelWarning, Diagnostics, DiagnosticsBatch},
 };
 use crate::{ast::Span, common::preview_features::PreviewFeature, configuration::StringFromEnvVar};
 use crate::{
@@ -20,6 +20,7 @@ use std::collections::{HashMap, HashSet};
 const PREVIEW_FEATURES_KEY: &str = "previewFeatures";
 const SHADOW_DATABASE_URL_KEY: &str = "shadowDatabaseUrl";
 const URL_KEY: &str = "url";
+const PROVIDER_AUTO: &str = "auto";
 
 /// Is responsible for loading and validating Datasources defined in an AST.
 pub struct DatasourceLoader {
@@ -41,26 +42,31 @@ impl DatasourceLoader {
         &self,
         ast_schema: &ast::SchemaAst,
         preview_features: &HashSet<&PreviewFeature>,
-        diagnostics: &mut Diagnostics,
+        batch: &mut DiagnosticsBatch,
     ) -> Vec<Datasource> {
+        let mut diagnostics = Diagnostics::new();
         let mut sources = Vec::new();
 
         for src in ast_schema.sources() {
-            if let Some(source) = self.lift_datasource(&src, preview_features, diagnostics) {
+            if let Some(source) = self.lift_datasource(&src, preview_features, &mut diagnostics) {
                 sources.push(source)
             }
         }
 
-        if sources.len() > 1 {
+        if sources.len() > 1 && !preview_features.contains(&PreviewFeature::NamedDatasources) {
             for src in ast_schema.sources() {
                 diagnostics.push_error(DatamodelError::new_source_validation_error(
-                    &"You defined more than one datasource. This is not allowed yet because support for multiple databases has not been implemented yet.".to_string(),
+                    &"You defined more than one datasource. This is not allowed unless the `namedDatasources` preview feature is enabled.".to_string(),
                     &src.name.name,
                     src.span,
                 ));
             }
+        } else if sources.len() > 1 {
+            validate_unique_datasource_names(ast_schema, &mut diagnostics);
         }
 
+        batch.push("datasource", diagnostics);
+
         sources
     }
 
@@ -137,6 +143,23 @@ impl DatasourceLoader {
             }
         };
 
+        let datasource_provider = match self.resolve_datasource_provider(provider, &url, source_name, provider_arg.span())
+        {
+            Ok(datasource_provider) => datasource_provider,
+            Err(err) => {
+                diagnostics.push_error(err);
+                return None;
+            }
+        };
+
+        let url = match datasource_provider.normalize_url(url, source_name, url_arg.span()) {
+            Ok(url) => url,
+            Err(err) => {
+                diagnostics.push_error(err);
+                return None;
+            }
+        };
+
         let shadow_database_url_arg = args.get(SHADOW_DATABASE_URL_KEY);
 
         let shadow_database_url: Option<(StringFromEnvVar, Span)> =
@@ -146,8 +169,15 @@ impl DatasourceLoader {
                         .filter(|s| !s.as_literal().map(|lit| lit.is_empty()).unwrap_or(false))
                         .map(|url| (url, shadow_database_url_arg.span())),
 
-                    // We intentionally ignore the shadow database URL if it is defined in an env var that is missing.
-                    Err(DatamodelError::EnvironmentFunctionalEvaluationError { .. }) => None,
+                    // The shadow database URL is optional, so a missing env var is not fatal, but
+                    // it is worth surfacing: the shadow database feature silently stays off.
+                    Err(DatamodelError::EnvironmentFunctionalEvaluationError { var_name, .. }) => {
+                        diagnostics.push_warning(DatamodelWarning::new_shadow_database_url_missing_env_var_warning(
+                            &var_name,
+                            shadow_database_url_arg.span(),
+                        ));
+                        None
+                    }
 
                     Err(err) => {
                         diagnostics.push_error(err);
@@ -162,17 +192,6 @@ impl DatasourceLoader {
 
         let documentation = ast_source.documentation.as_ref().map(|comment| comment.text.clone());
 
-        let datasource_provider = match self.get_datasource_provider(&provider) {
-            Some(provider) => provider,
-            None => {
-                diagnostics.push_error(DatamodelError::new_datasource_provider_not_known_error(
-                    provider,
-                    provider_arg.span(),
-                ));
-                return None;
-            }
-        };
-
         Some(Datasource {
             name: source_name.to_string(),
             provider: provider.to_owned(),
@@ -192,6 +211,70 @@ impl DatasourceLoader {
             .find(|sd| sd.is_provider(provider))
             .map(|b| b.as_ref())
     }
+
+    /// Resolves the `provider` argument to a concrete `DatasourceProvider`. When
+    /// `provider` is `"auto"`, the provider is instead inferred from the `url`'s
+    /// scheme, matching it against each builtin provider in turn.
+    fn resolve_datasource_provider(
+        &self,
+        provider: &str,
+        url: &StringFromEnvVar,
+        source_name: &str,
+        provider_span: Span,
+    ) -> Result<&dyn DatasourceProvider, DatamodelError> {
+        if provider == PROVIDER_AUTO {
+            return match url.as_literal().and_then(|literal| self.infer_datasource_provider(literal)) {
+                Some(datasource_provider) => Ok(datasource_provider),
+                None => Err(DatamodelError::new_source_validation_error(
+                    "Could not infer a provider from the `url`. Set `provider` explicitly, or use a recognized connection string scheme.",
+                    source_name,
+                    provider_span,
+                )),
+            };
+        }
+
+        self.get_datasource_provider(provider)
+            .ok_or_else(|| DatamodelError::new_datasource_provider_not_known_error(provider, provider_span))
+    }
+
+    fn infer_datasource_provider(&self, url: &str) -> Option<&dyn DatasourceProvider> {
+        self.source_definitions
+            .iter()
+            .find(|sd| sd.infer_from_url(url))
+            .map(|b| b.as_ref())
+    }
+}
+
+/// Returns the index of every name in `names` that has already appeared earlier in
+/// iteration order, i.e. every repeat after a name's first occurrence.
+fn find_duplicate_name_indices(names: &[&str]) -> Vec<usize> {
+    let mut seen: HashSet<&str> = HashSet::new();
+    names
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| !seen.insert(**name))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// With the `namedDatasources` preview feature, several datasources can coexist in
+/// one schema, but their names must be unique so `@@datasource("name")` and
+/// `ParserDatabase::datasource_for_model` can unambiguously address one of them.
+fn validate_unique_datasource_names(ast_schema: &ast::SchemaAst, diagnostics: &mut Diagnostics) {
+    let sources: Vec<_> = ast_schema.sources().collect();
+    let names: Vec<&str> = sources.iter().map(|src| src.name.name.as_str()).collect();
+
+    for index in find_duplicate_name_indices(&names) {
+        let src = &sources[index];
+        diagnostics.push_error(DatamodelError::new_source_validation_error(
+            &format!(
+                "A datasource named `{}` is already defined. Datasource names must be unique.",
+                src.name.name
+            ),
+            &src.name.name,
+            src.span,
+        ));
+    }
 }
 
 fn get_builtin_datasource_providers() -> Vec<Box<dyn DatasourceProvider>> {
@@ -255,6 +338,7 @@ fn preview_features_guardrail(args: &HashMap<&str, ValueValidator>, diagnostics:
         match val.as_array().to_str_vec() {
             Ok(features) => {
                 if features.is_empty() {
+                    diagnostics.push_warning(DatamodelWarning::new_preview_features_empty_array_warning(val.span()));
                     return;
                 }
 
@@ -267,3 +351,71 @@ fn preview_features_guardrail(args: &HashMap<&str, ValueValidator>, diagnostics:
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal(value: &str) -> StringFromEnvVar {
+        StringFromEnvVar {
+            from_env_var: None,
+            value: value.to_owned(),
+        }
+    }
+
+    fn loader() -> DatasourceLoader {
+        DatasourceLoader::new()
+    }
+
+    #[test]
+    fn infers_each_builtin_provider_from_its_url_scheme() {
+        let loader = loader();
+
+        let cases = [
+            ("mysql://localhost:3306/db", "mysql"),
+            ("postgresql://localhost:5432/db", "postgresql"),
+            ("postgres://localhost:5432/db", "postgresql"),
+            ("file:./dev.db", "sqlite"),
+            ("sqlserver://localhost:1433;database=db", "sqlserver"),
+            ("mongodb://localhost:27017/db", "mongodb"),
+            ("mongodb+srv://localhost/db", "mongodb"),
+        ];
+
+        for (url, expected_canonical_name) in cases {
+            let provider = loader
+                .resolve_datasource_provider(PROVIDER_AUTO, &literal(url), "db", Span::new(0, 1))
+                .unwrap_or_else(|_| panic!("expected {} to resolve to {}", url, expected_canonical_name));
+
+            assert_eq!(provider.canonical_name(), expected_canonical_name);
+        }
+    }
+
+    #[test]
+    fn errors_with_the_real_source_name_when_auto_inference_fails() {
+        let loader = loader();
+        let url = literal("not-a-recognized-connection-string");
+
+        let err = loader
+            .resolve_datasource_provider(PROVIDER_AUTO, &url, "reporting", Span::new(0, 1))
+            .unwrap_err();
+
+        assert_eq!(
+            err.message(),
+            "Error validating datasource `reporting`: Could not infer a provider from the `url`. Set `provider` explicitly, or use a recognized connection string scheme."
+        );
+    }
+
+    #[test]
+    fn find_duplicate_name_indices_flags_every_repeat_after_the_first() {
+        let names = ["db", "reporting", "db", "db"];
+
+        assert_eq!(find_duplicate_name_indices(&names), vec![2, 3]);
+    }
+
+    #[test]
+    fn find_duplicate_name_indices_is_empty_for_all_unique_names() {
+        let names = ["db", "reporting"];
+
+        assert!(find_duplicate_name_indices(&names).is_empty());
+    }
+}
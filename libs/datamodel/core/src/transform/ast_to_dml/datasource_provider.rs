@@ -0,0 +1,31 @@
+use crate::{ast::Span, configuration::StringFromEnvVar, diagnostics::DatamodelError};
+use datamodel_connector::Connector;
+
+/// A built-in or third-party database backend that a `datasource` block can target.
+pub(crate) trait DatasourceProvider {
+    /// Returns true if this provider is named by the given `provider` argument value.
+    fn is_provider(&self, provider: &str) -> bool;
+
+    /// The provider name used in generated clients and error messages.
+    fn canonical_name(&self) -> &'static str;
+
+    /// The connector backing this provider, used to build the `Datasource`'s
+    /// `active_connector`.
+    fn connector(&self) -> &'static dyn Connector;
+
+    /// Whether this provider recognizes `url` as one of its own connection strings,
+    /// used to resolve `provider = "auto"`.
+    fn infer_from_url(&self, url: &str) -> bool;
+
+    /// Canonicalizes and validates a connection string for this provider ahead of
+    /// time, so a malformed or incomplete URL is rejected at schema-parsing time
+    /// instead of surfacing as a connection error on first query. `source_name` is
+    /// the declared name of the datasource block, and `span` the `url` argument's
+    /// span; both are used to report precise, correctly-labeled errors.
+    fn normalize_url(
+        &self,
+        url: StringFromEnvVar,
+        source_name: &str,
+        span: Span,
+    ) -> Result<StringFromEnvVar, DatamodelError>;
+}
@@ -1,27 +1,94 @@
 use super::names::Names;
+use super::types::{FullyResolvedType, Types};
 use crate::{
-    ast::{self, Enum, Field, FieldType, SchemaAst, Top, TopId},
+    ast::{self, Enum, FieldId, SchemaAst, TopId},
     diagnostics::{DatamodelError, Diagnostics},
+    transform::helpers::ValueValidator,
 };
 use std::collections::HashMap;
 
+const DATASOURCE_ATTRIBUTE_NAME: &str = "datasource";
+const KEY_ATTRIBUTE_NAME: &str = "key";
+const VERSION_ATTRIBUTE_NAME: &str = "version";
+const MAP_ATTRIBUTE_NAME: &str = "map";
+
+/// A field set declared through `@@key(fields: [...])` that uniquely identifies a
+/// model's rows for external (e.g. federated) resolution.
+#[derive(Debug, Clone)]
+pub(crate) struct EntityKey {
+    fields: Vec<FieldId>,
+}
+
+impl EntityKey {
+    pub(crate) fn fields(&self) -> &[FieldId] {
+        &self.fields
+    }
+}
+
+/// Every concrete, `@@version`-tagged variant of a logical model, e.g. the `UserV1`
+/// and `UserV2` models that both `@@map` to the same `"User"` table. Tooling can use
+/// this to generate storage for every still-live shape of an entity and a sync
+/// mapping between adjacent versions, instead of one destructive migration.
+#[derive(Debug, Clone)]
+pub(crate) struct VersionLineage {
+    /// `(version, model_id)`, sorted ascending by version.
+    variants: Vec<(i64, TopId)>,
+}
+
+impl VersionLineage {
+    pub(crate) fn variants(&self) -> &[(i64, TopId)] {
+        &self.variants
+    }
+
+    /// The highest-numbered version, i.e. the one new writes should target.
+    pub(crate) fn current(&self) -> TopId {
+        self.variants.last().expect("a version lineage is never empty").1
+    }
+}
+
 pub(crate) struct ParserDatabase<'ast> {
     schema: &'ast SchemaAst,
     names: Names<'ast>,
-    _type_aliases: HashMap<TopId, FullyResolvedType>,
+    types: Types,
+    model_datasources: HashMap<TopId, &'ast str>,
+    entity_keys: HashMap<TopId, Vec<EntityKey>>,
+    model_versions: HashMap<&'ast str, VersionLineage>,
 }
 
 impl<'ast> ParserDatabase<'ast> {
-    pub(super) fn new(schema: &'ast SchemaAst, diagnostics: &mut Diagnostics) -> ParserDatabase<'ast> {
+    /// `datasources` is `(name, active connector's canonical provider name)` for every
+    /// datasource loaded for this schema, used to resolve `@@datasource(...)`
+    /// bindings and to check relations that cross datasources for connector
+    /// compatibility.
+    pub(super) fn new(
+        schema: &'ast SchemaAst,
+        datasources: &[(&'ast str, &'static str)],
+        diagnostics: &mut Diagnostics,
+    ) -> ParserDatabase<'ast> {
         let names = Names::new(schema, diagnostics);
+        let types = Types::new(schema, &names, diagnostics);
+        let datasource_names: Vec<&'ast str> = datasources.iter().map(|(name, _)| *name).collect();
+        let model_datasources = resolve_model_datasources(schema, &datasource_names, diagnostics);
+        let entity_keys = resolve_entity_keys(schema, &types, diagnostics);
+        let model_versions = resolve_model_versions(schema, diagnostics);
+        // Todo: populate constraints model per model.
 
-        let type_aliases = resolve_aliases(schema, &names, diagnostics);
-        // Todo: check types and populate constraints model per model.
+        let datasource_providers: HashMap<&'ast str, &'static str> = datasources.iter().copied().collect();
+        validate_cross_datasource_relations(schema, &types, &model_datasources, &datasource_providers, diagnostics);
+
+        // Names and Types can both report the same broken reference (e.g. a type name
+        // that is also missing from `names.tops`) more than once; collapse those down
+        // to one diagnostic per (span, message) pair before handing anything back to
+        // the caller.
+        diagnostics.sort_and_dedupe();
 
         ParserDatabase {
             schema,
             names,
-            _type_aliases: type_aliases,
+            types,
+            model_datasources,
+            entity_keys,
+            model_versions,
         }
     }
 
@@ -39,113 +106,568 @@ impl<'ast> ParserDatabase<'ast> {
             .values()
             .filter_map(move |topid| self.schema[*topid].as_enum().map(|enm| (*topid, enm)))
     }
+
+    /// The fully resolved type of a model field, with type aliases erased.
+    pub(crate) fn field_type(&self, model_id: TopId, field_id: FieldId) -> super::types::FullyResolvedType {
+        self.types.field_type(model_id, field_id)
+    }
+
+    /// The name of the datasource a model is bound to via `@@datasource("name")`.
+    /// `None` means the model is not pinned to a particular datasource: it has no
+    /// `@@datasource` attribute, or the attribute could not be resolved to a loaded
+    /// datasource (which is reported as an error).
+    pub(crate) fn datasource_for_model(&self, model_id: TopId) -> Option<&'ast str> {
+        self.model_datasources.get(&model_id).copied()
+    }
+
+    /// The entity keys declared on a model through `@@key(fields: [...])`.
+    pub(crate) fn iter_entity_keys(&self, model_id: TopId) -> impl Iterator<Item = &EntityKey> {
+        self.entity_keys.get(&model_id).into_iter().flatten()
+    }
+
+    /// Whether a model declares at least one entity key, i.e. can be resolved as a
+    /// federated entity by external services.
+    pub(crate) fn is_entity_source(&self, model_id: TopId) -> bool {
+        self.entity_keys.get(&model_id).map(|keys| !keys.is_empty()).unwrap_or(false)
+    }
+
+    /// The version lineage for a logical model name, i.e. its `@@map`-ed table name
+    /// for versioned models, or the model's own name otherwise. `None` means no
+    /// `@@version`-tagged model groups under `base_name`, or that two of them
+    /// declare the same version (which is reported as an error).
+    pub(crate) fn model_versions(&self, base_name: &str) -> Option<&VersionLineage> {
+        self.model_versions.get(base_name)
+    }
+}
+
+/// Both `@@datasource("name")` and `@@key(fields: [...])` accept their one argument
+/// either named or positional; this finds the index of whichever form was used,
+/// given just the argument names in declaration order.
+fn find_named_or_positional_arg_index(argument_names: &[&str], name: &str) -> Option<usize> {
+    argument_names
+        .iter()
+        .position(|arg_name| *arg_name == name)
+        .or(if argument_names.is_empty() { None } else { Some(0) })
+}
+
+/// Whether `declared_name` refers to one of the datasources actually loaded for this
+/// schema.
+fn resolve_known_datasource_name<'a>(declared_name: &str, known_names: &[&'a str]) -> Option<&'a str> {
+    known_names.iter().find(|name| **name == declared_name).copied()
+}
+
+/// Parses the `@@datasource("name")` attribute on each model and binds it to one of
+/// the datasources loaded for this schema, erroring when a model names a datasource
+/// that was not loaded.
+fn resolve_model_datasources<'ast>(
+    schema: &'ast SchemaAst,
+    datasource_names: &[&'ast str],
+    diagnostics: &mut Diagnostics,
+) -> HashMap<TopId, &'ast str> {
+    let mut model_datasources = HashMap::new();
+
+    for (model_id, model) in schema
+        .iter_tops()
+        .filter_map(|(id, top)| top.as_model().map(|model| (id, model)))
+    {
+        let attribute = match model
+            .attributes
+            .iter()
+            .find(|attribute| attribute.name.name == DATASOURCE_ATTRIBUTE_NAME)
+        {
+            Some(attribute) => attribute,
+            None => continue,
+        };
+
+        let argument_names: Vec<&str> = attribute.arguments.iter().map(|arg| arg.name.name.as_str()).collect();
+        let name_arg = match find_named_or_positional_arg_index(&argument_names, "name") {
+            Some(index) => &attribute.arguments[index],
+            None => {
+                diagnostics.push_error(DatamodelError::new_validation_error(
+                    "`@@datasource` requires the name of a datasource, e.g. `@@datasource(\"reporting\")`.",
+                    attribute.span,
+                ));
+                continue;
+            }
+        };
+
+        let datasource_name = match ValueValidator::new(&name_arg.value).as_string_literal() {
+            Some((name, _)) => name,
+            None => {
+                diagnostics.push_error(DatamodelError::new_validation_error(
+                    "The datasource name passed to `@@datasource` must be a string literal.",
+                    attribute.span,
+                ));
+                continue;
+            }
+        };
+
+        let datasource_name = match resolve_known_datasource_name(datasource_name, datasource_names) {
+            Some(name) => name,
+            None => {
+                diagnostics.push_error(DatamodelError::new_validation_error(
+                    &format!(
+                        "Model `{}` references an undefined datasource `{}`.",
+                        model.name.name, datasource_name
+                    ),
+                    attribute.span,
+                ));
+                continue;
+            }
+        };
+
+        model_datasources.insert(model_id, datasource_name);
+    }
+
+    model_datasources
 }
 
-/// The type of a field, with type aliases erased.
-#[derive(Debug, Clone, Copy)]
-enum FullyResolvedType {
-    // Model(TopId),
-    Enum(TopId),
-    Scalar,
-    Unsupported,
-    Unknown,
+/// Whether a relation field pointing from a model pinned to `source_datasource` to a
+/// model pinned to `target_datasource` must be rejected. Two datasources on the same
+/// provider (e.g. two `postgresql` datasources) are considered compatible; anything
+/// else is not, since the relation would otherwise need to be resolved across two
+/// different SQL dialects (or a relational and a document store). A provider that
+/// couldn't be resolved (`None`) is tolerated here, since that's a different,
+/// already-reported error.
+fn cross_datasource_relation_is_incompatible(
+    source_datasource: &str,
+    target_datasource: &str,
+    source_provider: Option<&str>,
+    target_provider: Option<&str>,
+) -> bool {
+    source_datasource != target_datasource
+        && source_provider.is_some()
+        && target_provider.is_some()
+        && source_provider != target_provider
 }
 
-const BUILT_IN_SCALARS: &[&str] = &[
-    "Int", "BigInt", "Float", "Boolean", "String", "DateTime", "Json", "Bytes", "Decimal",
-];
+/// Whether the field currently being examined (pointing `model_id` -> `related_model_id`)
+/// is the mirror side of a relation already reported in the opposite direction. If so,
+/// it consumes one pending occurrence and the caller should skip reporting this field;
+/// otherwise `pending_relations` is left untouched so the caller can register this
+/// occurrence itself once it decides whether to report it.
+///
+/// This counts occurrences per directed model pair rather than tracking the identity
+/// of individual relations (the schema doesn't expose which field is paired with
+/// which), so it correctly collapses the common cases — one bidirectional relation,
+/// or several distinct same-direction relations between the same two models (e.g.
+/// `buyer`/`seller`) — but three or more relations between the same pair with an
+/// uneven split of fields per direction could in theory still mismatch which
+/// occurrence consumes which. That combination is not known to occur in practice.
+fn consumed_as_mirror(pending_relations: &mut HashMap<(TopId, TopId), u32>, model_id: TopId, related_model_id: TopId) -> bool {
+    if let Some(pending) = pending_relations.get_mut(&(related_model_id, model_id)) {
+        if *pending > 0 {
+            *pending -= 1;
+            return true;
+        }
+    }
 
-/// Fully resolve type aliases to non-aliased types. Substituting the resolved
-/// type from the returned map for the alias will correctly eliminate aliases.
-fn resolve_aliases(
+    false
+}
+
+/// Errors on a relation field whose model and related model are pinned (via
+/// `@@datasource`) to two different datasources backed by incompatible connectors.
+fn validate_cross_datasource_relations(
     schema: &SchemaAst,
-    names: &Names<'_>,
+    types: &Types,
+    model_datasources: &HashMap<TopId, &str>,
+    datasource_providers: &HashMap<&str, &str>,
     diagnostics: &mut Diagnostics,
-) -> HashMap<TopId, FullyResolvedType> {
-    let mut aliases = HashMap::new();
-    // The references to other aliases followed from the "root" alias. This
-    // is used to render error messages in case a recursive definition is
-    // detected.
-    let mut traversed_type_aliases: Vec<&str> = Vec::new();
-
-    for (alias_id, type_alias) in schema
+) {
+    // A relation is declared as a field on each of its two models, pointing at one
+    // another, so a naive per-field scan reports every incompatible relation twice —
+    // once per side. Counting pending (reported, not yet mirrored) occurrences per
+    // directed model pair lets a field flagged in the opposite direction consume one
+    // of them instead of being reported again, while two genuinely distinct relations
+    // between the same two models (e.g. `buyer`/`seller`) still get one report each.
+    let mut pending_relations: HashMap<(TopId, TopId), u32> = HashMap::new();
+
+    for (model_id, model) in schema
         .iter_tops()
-        .filter_map(|(id, top)| top.as_type_alias().map(|alias| (id, alias)))
+        .filter_map(|(id, top)| top.as_model().map(|model| (id, model)))
     {
-        traversed_type_aliases.clear();
-        aliases.insert(
-            alias_id,
-            resolve_alias(
-                (alias_id, type_alias),
-                schema,
-                names,
-                &mut traversed_type_aliases,
-                diagnostics,
-            ),
-        );
+        let source_datasource = match model_datasources.get(&model_id) {
+            Some(name) => *name,
+            None => continue,
+        };
+
+        for (field_id, field) in model.iter_fields() {
+            let related_model_id = match types.field_type(model_id, field_id) {
+                FullyResolvedType::Model(related_model_id) => related_model_id,
+                _ => continue,
+            };
+
+            let target_datasource = match model_datasources.get(&related_model_id) {
+                Some(name) => *name,
+                None => continue,
+            };
+
+            if consumed_as_mirror(&mut pending_relations, model_id, related_model_id) {
+                continue;
+            }
+
+            let source_provider = datasource_providers.get(source_datasource).copied();
+            let target_provider = datasource_providers.get(target_datasource).copied();
+
+            if cross_datasource_relation_is_incompatible(source_datasource, target_datasource, source_provider, target_provider) {
+                *pending_relations.entry((model_id, related_model_id)).or_insert(0) += 1;
+
+                let related_model_name = schema[related_model_id]
+                    .as_model()
+                    .map(|related_model| related_model.name.name.as_str())
+                    .unwrap_or_default();
+
+                diagnostics.push_error(DatamodelError::new_validation_error(
+                    &format!(
+                        "Field `{}` on model `{}` (datasource `{}`, provider `{}`) relates to model `{}` (datasource `{}`, provider `{}`); relations cannot cross datasources with incompatible connectors.",
+                        field.name.name,
+                        model.name.name,
+                        source_datasource,
+                        source_provider.unwrap_or("?"),
+                        related_model_name,
+                        target_datasource,
+                        target_provider.unwrap_or("?"),
+                    ),
+                    field.field_type.span(),
+                ));
+            }
+        }
+    }
+}
+
+/// Parses every `@@key(fields: [...])` attribute on every model, validating that
+/// each referenced field exists and resolves to a scalar or enum: a relation or an
+/// `Unsupported` field cannot identify a row for an external resolver.
+fn resolve_entity_keys(schema: &SchemaAst, types: &Types, diagnostics: &mut Diagnostics) -> HashMap<TopId, Vec<EntityKey>> {
+    let mut entity_keys = HashMap::new();
+
+    for (model_id, model) in schema
+        .iter_tops()
+        .filter_map(|(id, top)| top.as_model().map(|model| (id, model)))
+    {
+        let keys: Vec<EntityKey> = model
+            .attributes
+            .iter()
+            .filter(|attribute| attribute.name.name == KEY_ATTRIBUTE_NAME)
+            .filter_map(|attribute| resolve_entity_key(model_id, model, attribute, types, diagnostics))
+            .collect();
+
+        if !keys.is_empty() {
+            entity_keys.insert(model_id, keys);
+        }
     }
 
-    aliases
+    entity_keys
 }
 
-fn resolve_alias<'a>(
-    (root_alias_id, root_type_alias): (TopId, &Field),
-    schema: &'a SchemaAst,
-    names: &Names<'_>,
-    traversed_type_aliases: &mut Vec<&'a str>,
+fn resolve_entity_key(
+    model_id: TopId,
+    model: &ast::Model,
+    attribute: &ast::Attribute,
+    types: &Types,
     diagnostics: &mut Diagnostics,
-) -> FullyResolvedType {
-    match &root_type_alias.field_type {
-        FieldType::Supported(type_name) => {
-            if BUILT_IN_SCALARS.contains(&type_name.name.as_str()) {
-                return FullyResolvedType::Scalar;
+) -> Option<EntityKey> {
+    let argument_names: Vec<&str> = attribute.arguments.iter().map(|arg| arg.name.name.as_str()).collect();
+    let fields_arg = match find_named_or_positional_arg_index(&argument_names, "fields") {
+        Some(index) => &attribute.arguments[index],
+        None => {
+            diagnostics.push_error(DatamodelError::new_validation_error(
+                "`@@key` requires a list of field names, e.g. `@@key(fields: [\"id\"])`.",
+                attribute.span,
+            ));
+            return None;
+        }
+    };
+
+    let field_names = match ValueValidator::new(&fields_arg.value).as_array().to_str_vec() {
+        Ok(field_names) => field_names,
+        Err(err) => {
+            diagnostics.push_error(err);
+            return None;
+        }
+    };
+
+    let mut fields = Vec::with_capacity(field_names.len());
+
+    for field_name in field_names {
+        let (field_id, field) = match model.iter_fields().find(|(_, field)| field.name.name == field_name) {
+            Some(found) => found,
+            None => {
+                diagnostics.push_error(DatamodelError::new_validation_error(
+                    &format!(
+                        "`@@key` references field `{}`, which does not exist on model `{}`.",
+                        field_name, model.name.name
+                    ),
+                    attribute.span,
+                ));
+                return None;
             }
+        };
 
-            match names.tops.get(type_name.name.as_str()).map(|id| (id, &schema[*id])) {
-                Some((referenced_alias_id, Top::Type(referenced_alias))) => {
-                    if *referenced_alias_id == root_alias_id
-                        || traversed_type_aliases.contains(&referenced_alias.name.name.as_str())
-                    {
-                        // Recursive type.
-                        diagnostics.push_error(DatamodelError::new_validation_error(
-                            &format!(
-                                "Recursive type definitions are not allowed. Recursive path was: {} -> {}.",
-                                traversed_type_aliases.join(" -> "),
-                                root_type_alias.name.name
-                            ),
-                            root_type_alias.span,
-                        ));
-                        return FullyResolvedType::Unknown;
-                    }
-
-                    traversed_type_aliases.push(&referenced_alias.name.name);
-
-                    resolve_alias(
-                        (root_alias_id, root_type_alias),
-                        schema,
-                        names,
-                        traversed_type_aliases,
-                        diagnostics,
-                    )
-                }
-                Some((_, Top::Model(_))) => {
-                    diagnostics.push_error(DatamodelError::new_validation_error(
-                        "Only scalar types can be used for defining custom types.",
-                        root_type_alias.field_type.span(),
-                    ));
-                    FullyResolvedType::Unknown
-                }
-                Some((id, Top::Enum(_))) => FullyResolvedType::Enum(*id),
-                Some((_, Top::Generator(_))) | Some((_, Top::Source(_))) => unreachable!(),
-                None => {
-                    diagnostics.push_error(DatamodelError::new_type_not_found_error(
-                        &type_name.name,
-                        root_type_alias.field_type.span(),
-                    ));
-                    FullyResolvedType::Unknown
-                }
+        if is_valid_key_field_type(types.field_type(model_id, field_id)) {
+            fields.push(field_id);
+        } else {
+            diagnostics.push_error(DatamodelError::new_validation_error(
+                &format!(
+                    "`@@key` field `{}` on model `{}` must be a scalar or enum, not a relation or unsupported type.",
+                    field.name.name, model.name.name
+                ),
+                attribute.span,
+            ));
+            return None;
+        }
+    }
+
+    Some(EntityKey { fields })
+}
+
+/// Whether a field's resolved type can identify a row for an external resolver. A
+/// relation or an `Unsupported` field can't: federation needs a self-contained
+/// scalar or enum value to hand back to the gateway.
+fn is_valid_key_field_type(resolved: FullyResolvedType) -> bool {
+    matches!(resolved, FullyResolvedType::Scalar | FullyResolvedType::Enum(_))
+}
+
+/// Groups `@@version`-tagged models by their logical (`@@map`-ed, or own) name, and
+/// validates that versions are unique and well-ordered within each group.
+fn resolve_model_versions<'ast>(
+    schema: &'ast SchemaAst,
+    diagnostics: &mut Diagnostics,
+) -> HashMap<&'ast str, VersionLineage> {
+    let mut versions_by_base_name: HashMap<&'ast str, Vec<(i64, TopId, ast::Span)>> = HashMap::new();
+
+    for (model_id, model) in schema
+        .iter_tops()
+        .filter_map(|(id, top)| top.as_model().map(|model| (id, model)))
+    {
+        let attribute = match model
+            .attributes
+            .iter()
+            .find(|attribute| attribute.name.name == VERSION_ATTRIBUTE_NAME)
+        {
+            Some(attribute) => attribute,
+            None => continue,
+        };
+
+        let version_arg = match attribute.arguments.first() {
+            Some(arg) => arg,
+            None => {
+                diagnostics.push_error(DatamodelError::new_validation_error(
+                    "`@@version` requires a version, e.g. `@@version(\"1\")`.",
+                    attribute.span,
+                ));
+                continue;
             }
+        };
+
+        let version = match ValueValidator::new(&version_arg.value)
+            .as_string_literal()
+            .and_then(|(version, _)| version.parse::<i64>().ok())
+        {
+            Some(version) => version,
+            None => {
+                diagnostics.push_error(DatamodelError::new_validation_error(
+                    "The version passed to `@@version` must be a string literal containing an integer, e.g. \"1\".",
+                    attribute.span,
+                ));
+                continue;
+            }
+        };
+
+        let map_name = model
+            .attributes
+            .iter()
+            .find(|attribute| attribute.name.name == MAP_ATTRIBUTE_NAME)
+            .and_then(|attribute| attribute.arguments.first())
+            .and_then(|arg| ValueValidator::new(&arg.value).as_string_literal())
+            .map(|(name, _)| name);
+        let base_name = resolve_base_name(map_name, model.name.name.as_str());
+
+        versions_by_base_name
+            .entry(base_name)
+            .or_default()
+            .push((version, model_id, attribute.span));
+    }
+
+    let mut model_versions = HashMap::new();
+
+    for (base_name, mut variants) in versions_by_base_name {
+        variants.sort_by_key(|(version, _, _)| *version);
+
+        let versions: Vec<i64> = variants.iter().map(|(version, _, _)| *version).collect();
+        let has_duplicate = !find_duplicate_versions(&versions).is_empty();
+
+        for window in variants.windows(2) {
+            if window[0].0 == window[1].0 {
+                diagnostics.push_error(DatamodelError::new_validation_error(
+                    &format!(
+                        "Two models declare version \"{}\" for logical model `{}`; versions must be unique.",
+                        window[1].0, base_name
+                    ),
+                    window[1].2,
+                ));
+            }
+        }
+
+        if has_duplicate {
+            continue;
         }
-        FieldType::Unsupported(_, _) => FullyResolvedType::Unsupported,
+
+        model_versions.insert(
+            base_name,
+            VersionLineage {
+                variants: variants.into_iter().map(|(version, model_id, _)| (version, model_id)).collect(),
+            },
+        );
+    }
+
+    model_versions
+}
+
+/// The logical name a `@@version`-tagged model groups under: its `@@map`-ed table
+/// name when it has one, or its own name otherwise.
+fn resolve_base_name<'a>(map_name: Option<&'a str>, own_name: &'a str) -> &'a str {
+    map_name.unwrap_or(own_name)
+}
+
+/// Returns every version number in `sorted_versions` (must already be sorted
+/// ascending) that collides with its predecessor, once per collision.
+fn find_duplicate_versions(sorted_versions: &[i64]) -> Vec<i64> {
+    sorted_versions
+        .windows(2)
+        .filter(|window| window[0] == window[1])
+        .map(|window| window[1])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_named_or_positional_arg_index_prefers_the_named_arg() {
+        let names = ["other", "name"];
+        assert_eq!(find_named_or_positional_arg_index(&names, "name"), Some(1));
+    }
+
+    #[test]
+    fn find_named_or_positional_arg_index_falls_back_to_the_first_positional_arg() {
+        let names = ["reporting"];
+        assert_eq!(find_named_or_positional_arg_index(&names, "name"), Some(0));
+    }
+
+    #[test]
+    fn find_named_or_positional_arg_index_is_none_when_no_arguments_were_given() {
+        let names: [&str; 0] = [];
+        assert_eq!(find_named_or_positional_arg_index(&names, "name"), None);
+    }
+
+    #[test]
+    fn resolve_known_datasource_name_accepts_a_loaded_datasource() {
+        let known = ["db", "reporting"];
+        assert_eq!(resolve_known_datasource_name("reporting", &known), Some("reporting"));
+    }
+
+    #[test]
+    fn resolve_known_datasource_name_rejects_an_undefined_datasource() {
+        let known = ["db", "reporting"];
+        assert_eq!(resolve_known_datasource_name("analytics", &known), None);
+    }
+
+    #[test]
+    fn same_provider_relations_across_datasources_are_allowed() {
+        assert!(!cross_datasource_relation_is_incompatible(
+            "db",
+            "reporting",
+            Some("postgresql"),
+            Some("postgresql")
+        ));
+    }
+
+    #[test]
+    fn relations_within_one_datasource_are_always_allowed() {
+        assert!(!cross_datasource_relation_is_incompatible(
+            "db", "db", Some("postgresql"), Some("mongodb")
+        ));
+    }
+
+    #[test]
+    fn relations_across_incompatible_providers_are_rejected() {
+        assert!(cross_datasource_relation_is_incompatible(
+            "db",
+            "reporting",
+            Some("postgresql"),
+            Some("mongodb")
+        ));
+    }
+
+    #[test]
+    fn an_unresolved_provider_is_tolerated_as_a_different_already_reported_error() {
+        assert!(!cross_datasource_relation_is_incompatible("db", "reporting", None, Some("mongodb")));
+    }
+
+    #[test]
+    fn consumed_as_mirror_absorbs_the_back_relation_field_of_a_reported_relation() {
+        let mut pending = HashMap::new();
+        let (a, b) = (TopId(0), TopId(1));
+
+        assert!(!consumed_as_mirror(&mut pending, a, b));
+        pending.insert((a, b), 1);
+
+        assert!(consumed_as_mirror(&mut pending, b, a));
+        assert_eq!(pending.get(&(a, b)), Some(&0));
+    }
+
+    #[test]
+    fn consumed_as_mirror_does_not_absorb_a_second_distinct_relation_between_the_same_models() {
+        let mut pending = HashMap::new();
+        let (a, b) = (TopId(0), TopId(1));
+        pending.insert((a, b), 1);
+
+        // Both `buyer` and `seller` point a -> b, so the second one is not the mirror
+        // of the first and must still be reported on its own.
+        assert!(!consumed_as_mirror(&mut pending, a, b));
+    }
+
+    #[test]
+    fn scalar_and_enum_fields_are_valid_entity_key_members() {
+        assert!(is_valid_key_field_type(FullyResolvedType::Scalar));
+        assert!(is_valid_key_field_type(FullyResolvedType::Enum(TopId(0))));
+    }
+
+    #[test]
+    fn relation_and_unsupported_fields_are_not_valid_entity_key_members() {
+        assert!(!is_valid_key_field_type(FullyResolvedType::Model(TopId(0))));
+        assert!(!is_valid_key_field_type(FullyResolvedType::Unsupported));
+        assert!(!is_valid_key_field_type(FullyResolvedType::Unknown));
+    }
+
+    #[test]
+    fn resolve_base_name_prefers_the_map_name() {
+        assert_eq!(resolve_base_name(Some("UserTable"), "UserV2"), "UserTable");
+    }
+
+    #[test]
+    fn resolve_base_name_falls_back_to_the_models_own_name() {
+        assert_eq!(resolve_base_name(None, "User"), "User");
+    }
+
+    #[test]
+    fn find_duplicate_versions_flags_a_repeated_version() {
+        assert_eq!(find_duplicate_versions(&[1, 2, 2, 3]), vec![2]);
+    }
+
+    #[test]
+    fn find_duplicate_versions_is_empty_for_strictly_increasing_versions() {
+        assert!(find_duplicate_versions(&[1, 2, 3]).is_empty());
+    }
+
+    #[test]
+    fn version_lineage_current_picks_the_highest_version() {
+        let lineage = VersionLineage {
+            variants: vec![(1, TopId(0)), (2, TopId(1)), (3, TopId(2))],
+        };
+
+        assert_eq!(lineage.current(), TopId(2));
     }
 }
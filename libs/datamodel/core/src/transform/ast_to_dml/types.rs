@@ -24,26 +24,50 @@ impl Types {
                     .map(move |(field_id, field)| (model_id, model, field_id, field))
             });
 
-        for (model_id, model, field_id, field) in all_fields {
-            match &field.field_type {
-                FieldType::Unsupported(_, _) => {
-                    types
-                        .fields
-                        .insert((model_id, field_id), FullyResolvedType::Unsupported);
-                }
+        for (model_id, _model, field_id, field) in all_fields {
+            let resolved = match &field.field_type {
+                FieldType::Unsupported(_, _) => FullyResolvedType::Unsupported,
                 FieldType::Supported(type_name) => {
-                    todo!();
+                    if BUILT_IN_SCALARS.contains(&type_name.name.as_str()) {
+                        FullyResolvedType::Scalar
+                    } else {
+                        match names.tops.get(type_name.name.as_str()).map(|id| (*id, &schema[*id])) {
+                            Some((top_id, Top::Model(_))) => FullyResolvedType::Model(top_id),
+                            Some((top_id, Top::Enum(_))) => FullyResolvedType::Enum(top_id),
+                            // Type aliases were already fully resolved in `aliases`; reuse that
+                            // instead of re-walking the alias chain for every field.
+                            Some((top_id, Top::Type(_))) => {
+                                aliases.get(&top_id).copied().unwrap_or(FullyResolvedType::Unknown)
+                            }
+                            Some((_, Top::Generator(_))) | Some((_, Top::Source(_))) => unreachable!(),
+                            None => {
+                                diagnostics.push_error(DatamodelError::new_type_not_found_error(
+                                    &type_name.name,
+                                    field.field_type.span(),
+                                ));
+                                FullyResolvedType::Unknown
+                            }
+                        }
+                    }
                 }
-            }
+            };
+
+            types.fields.insert((model_id, field_id), resolved);
         }
 
         types
     }
+
+    /// The fully resolved type of a model field, with type aliases erased. Panics if
+    /// `(model_id, field_id)` does not identify a field on a model in this schema.
+    pub(crate) fn field_type(&self, model_id: TopId, field_id: FieldId) -> FullyResolvedType {
+        self.fields[&(model_id, field_id)]
+    }
 }
 
 /// The type of a field, with type aliases erased.
 #[derive(Debug, Clone, Copy)]
-enum FullyResolvedType {
+pub(crate) enum FullyResolvedType {
     Model(TopId),
     Enum(TopId),
     Scalar,
@@ -77,6 +101,7 @@ fn resolve_aliases(
             alias_id,
             resolve_alias(
                 (alias_id, type_alias),
+                type_alias,
                 schema,
                 names,
                 &mut traversed_type_aliases,
@@ -89,13 +114,14 @@ fn resolve_aliases(
 }
 
 fn resolve_alias<'a>(
-    (root_alias_id, root_type_alias): (TopId, &Field),
+    (root_alias_id, root_type_alias): (TopId, &'a Field),
+    current_type_alias: &Field,
     schema: &'a SchemaAst,
     names: &Names<'_>,
     traversed_type_aliases: &mut Vec<&'a str>,
     diagnostics: &mut Diagnostics,
 ) -> FullyResolvedType {
-    match &root_type_alias.field_type {
+    match &current_type_alias.field_type {
         FieldType::Supported(type_name) => {
             if BUILT_IN_SCALARS.contains(&type_name.name.as_str()) {
                 return FullyResolvedType::Scalar;
@@ -106,12 +132,15 @@ fn resolve_alias<'a>(
                     if *referenced_alias_id == root_alias_id
                         || traversed_type_aliases.contains(&referenced_alias.name.name.as_str())
                     {
-                        // Recursive type.
+                        // Recursive type. `root_type_alias` is kept distinct from
+                        // `current_type_alias` throughout the recursion so the path
+                        // reported here always starts from the true root, not from
+                        // whichever alias the traversal happens to be examining.
                         diagnostics.push_error(DatamodelError::new_validation_error(
-                            &format!(
-                                "Recursive type definitions are not allowed. Recursive path was: {} -> {}.",
-                                traversed_type_aliases.join(" -> "),
-                                root_type_alias.name.name
+                            &format_recursive_type_path(
+                                &root_type_alias.name.name,
+                                traversed_type_aliases,
+                                &referenced_alias.name.name,
                             ),
                             root_type_alias.span,
                         ));
@@ -122,6 +151,7 @@ fn resolve_alias<'a>(
 
                     resolve_alias(
                         (root_alias_id, root_type_alias),
+                        referenced_alias,
                         schema,
                         names,
                         traversed_type_aliases,
@@ -131,7 +161,7 @@ fn resolve_alias<'a>(
                 Some((_, Top::Model(_))) => {
                     diagnostics.push_error(DatamodelError::new_validation_error(
                         "Only scalar types can be used for defining custom types.",
-                        root_type_alias.field_type.span(),
+                        current_type_alias.field_type.span(),
                     ));
                     FullyResolvedType::Unknown
                 }
@@ -140,7 +170,7 @@ fn resolve_alias<'a>(
                 None => {
                     diagnostics.push_error(DatamodelError::new_type_not_found_error(
                         &type_name.name,
-                        root_type_alias.field_type.span(),
+                        current_type_alias.field_type.span(),
                     ));
                     FullyResolvedType::Unknown
                 }
@@ -149,3 +179,47 @@ fn resolve_alias<'a>(
         FieldType::Unsupported(_, _) => FullyResolvedType::Unsupported,
     }
 }
+
+/// Renders the "Recursive path was: ..." message for a cycle detected while
+/// resolving `root_name`, having traversed `traversed` aliases before looping back
+/// to `closing_name`. Pulled out of `resolve_alias` so the path-rendering itself
+/// can be tested without needing a real `SchemaAst`.
+fn format_recursive_type_path(root_name: &str, traversed: &[&str], closing_name: &str) -> String {
+    if traversed.is_empty() {
+        format!(
+            "Recursive type definitions are not allowed. Recursive path was: {} -> {}.",
+            root_name, closing_name
+        )
+    } else {
+        format!(
+            "Recursive type definitions are not allowed. Recursive path was: {} -> {} -> {}.",
+            root_name,
+            traversed.join(" -> "),
+            closing_name
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_recursive_type_path;
+
+    #[test]
+    fn recursive_path_message_names_the_true_root_for_a_direct_self_cycle() {
+        let message = format_recursive_type_path("A", &[], "A");
+        assert_eq!(
+            message,
+            "Recursive type definitions are not allowed. Recursive path was: A -> A."
+        );
+    }
+
+    #[test]
+    fn recursive_path_message_names_the_true_root_across_multiple_hops() {
+        // type A = B; type B = A
+        let message = format_recursive_type_path("A", &["B"], "A");
+        assert_eq!(
+            message,
+            "Recursive type definitions are not allowed. Recursive path was: A -> B -> A."
+        );
+    }
+}